@@ -0,0 +1,87 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use sdl2::audio::{AudioCallback, AudioDevice, AudioSpecDesired};
+use sdl2::{AudioSubsystem, Sdl};
+
+const SAMPLE_RATE: i32 = 44100;
+const DEFAULT_FREQUENCY: f32 = 440.0;
+// Coefficient of the one-pole low-pass on the gate signal: the fraction of the
+// remaining distance to the target amplitude covered each sample. Smaller is a
+// slower ramp, which is what keeps the attack/decay from clicking.
+const ENVELOPE_STEP: f32 = 0.005;
+
+struct SquareWave {
+    gate: Arc<AtomicBool>,
+    phase: f32,
+    phase_step: f32,
+    amplitude: f32,
+}
+
+impl AudioCallback for SquareWave {
+    type Channel = f32;
+
+    fn callback(&mut self, out: &mut [f32]) {
+        let target = if self.gate.load(Ordering::Relaxed) {
+            1.0
+        } else {
+            0.0
+        };
+
+        for sample in out.iter_mut() {
+            self.amplitude += (target - self.amplitude) * ENVELOPE_STEP;
+            *sample = if self.phase < 0.5 {
+                self.amplitude
+            } else {
+                -self.amplitude
+            };
+
+            self.phase = (self.phase + self.phase_step) % 1.0;
+        }
+    }
+}
+
+/// Plays a square-wave tone through SDL's audio callback whenever the CHIP-8
+/// sound timer is active, ramping amplitude on/off to avoid clicking.
+pub struct Beeper {
+    _device: AudioDevice<SquareWave>,
+    gate: Arc<AtomicBool>,
+}
+
+impl Beeper {
+    pub fn new(sdl_context: &Sdl) -> Result<Beeper, String> {
+        Beeper::with_frequency(sdl_context, DEFAULT_FREQUENCY)
+    }
+
+    pub fn with_frequency(sdl_context: &Sdl, frequency: f32) -> Result<Beeper, String> {
+        let audio_subsystem: AudioSubsystem = sdl_context.audio()?;
+
+        let gate = Arc::new(AtomicBool::new(false));
+        let callback_gate = Arc::clone(&gate);
+
+        let spec = AudioSpecDesired {
+            freq: Some(SAMPLE_RATE),
+            channels: Some(1),
+            samples: None,
+        };
+
+        let device = audio_subsystem.open_playback(None, &spec, |spec| SquareWave {
+            gate: callback_gate,
+            phase: 0.0,
+            phase_step: frequency / spec.freq as f32,
+            amplitude: 0.0,
+        })?;
+
+        device.resume();
+
+        Ok(Beeper {
+            _device: device,
+            gate,
+        })
+    }
+
+    /// Reflects whether the CHIP-8 sound timer is currently active.
+    pub fn set_active(&self, active: bool) {
+        self.gate.store(active, Ordering::Relaxed);
+    }
+}
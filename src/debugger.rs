@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+
+use crate::error::Error;
+use crate::processor::Processor;
+
+/// Result of driving the processor under [`Debugger::continue_run`].
+pub enum Outcome {
+    /// The program counter reached a registered breakpoint, before executing
+    /// the instruction there.
+    Breakpoint(u16),
+}
+
+/// PC breakpoints and single-step/continue control for a [`Processor`],
+/// usable from a REPL or a test harness that wants deterministic control
+/// over execution.
+pub struct Debugger {
+    breakpoints: HashSet<u16>,
+}
+
+impl Default for Debugger {
+    fn default() -> Debugger {
+        Debugger::new()
+    }
+}
+
+impl Debugger {
+    pub fn new() -> Debugger {
+        Debugger {
+            breakpoints: HashSet::new(),
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, addr: u16) {
+        self.breakpoints.insert(addr);
+    }
+
+    pub fn remove_breakpoint(&mut self, addr: u16) {
+        self.breakpoints.remove(&addr);
+    }
+
+    pub fn has_breakpoint(&self, addr: u16) -> bool {
+        self.breakpoints.contains(&addr)
+    }
+
+    /// Executes exactly one fetch/execute cycle, ignoring breakpoints.
+    pub fn step(&self, processor: &mut Processor) -> Result<(), Error> {
+        processor.cycle()
+    }
+
+    /// Runs until the program counter hits a registered breakpoint or the
+    /// processor errors.
+    pub fn continue_run(&self, processor: &mut Processor) -> Result<Outcome, Error> {
+        loop {
+            if self.breakpoints.contains(&processor.program_counter()) {
+                return Ok(Outcome::Breakpoint(processor.program_counter()));
+            }
+
+            processor.cycle()?;
+        }
+    }
+
+    /// Decodes `instruction` into its mnemonic form, e.g. `DRW V0, V1, 5`.
+    pub fn disassemble(instruction: u16) -> String {
+        let nibbles = (
+            (instruction & 0xF000) >> 12,
+            (instruction & 0x0F00) >> 8,
+            (instruction & 0x00F0) >> 4,
+            instruction & 0x000F,
+        );
+
+        let x = nibbles.1;
+        let y = nibbles.2;
+        let n = nibbles.3;
+        let kk = instruction & 0x00FF;
+        let nnn = instruction & 0x0FFF;
+
+        match nibbles {
+            (0x0, 0x0, 0xE, 0x0) => "CLS".to_string(),
+            (0x0, 0x0, 0xE, 0xE) => "RET".to_string(),
+            (0x0, _, _, _) => format!("SYS {:#05X}", nnn),
+            (0x1, _, _, _) => format!("JP {:#05X}", nnn),
+            (0x2, _, _, _) => format!("CALL {:#05X}", nnn),
+            (0x3, _, _, _) => format!("SE V{:X}, {:#04X}", x, kk),
+            (0x4, _, _, _) => format!("SNE V{:X}, {:#04X}", x, kk),
+            (0x5, _, _, 0x0) => format!("SE V{:X}, V{:X}", x, y),
+            (0x6, _, _, _) => format!("LD V{:X}, {:#04X}", x, kk),
+            (0x7, _, _, _) => format!("ADD V{:X}, {:#04X}", x, kk),
+            (0x8, _, _, 0x0) => format!("LD V{:X}, V{:X}", x, y),
+            (0x8, _, _, 0x1) => format!("OR V{:X}, V{:X}", x, y),
+            (0x8, _, _, 0x2) => format!("AND V{:X}, V{:X}", x, y),
+            (0x8, _, _, 0x3) => format!("XOR V{:X}, V{:X}", x, y),
+            (0x8, _, _, 0x4) => format!("ADD V{:X}, V{:X}", x, y),
+            (0x8, _, _, 0x5) => format!("SUB V{:X}, V{:X}", x, y),
+            (0x8, _, _, 0x6) => format!("SHR V{:X} {{, V{:X}}}", x, y),
+            (0x8, _, _, 0x7) => format!("SUBN V{:X}, V{:X}", x, y),
+            (0x8, _, _, 0xE) => format!("SHL V{:X} {{, V{:X}}}", x, y),
+            (0x9, _, _, 0x0) => format!("SNE V{:X}, V{:X}", x, y),
+            (0xA, _, _, _) => format!("LD I, {:#05X}", nnn),
+            (0xB, _, _, _) => format!("JP V0, {:#05X}", nnn),
+            (0xC, _, _, _) => format!("RND V{:X}, {:#04X}", x, kk),
+            (0xD, _, _, _) => format!("DRW V{:X}, V{:X}, {:X}", x, y, n),
+            (0xE, _, 0x9, 0xE) => format!("SKP V{:X}", x),
+            (0xE, _, 0xA, 0x1) => format!("SKNP V{:X}", x),
+            (0xF, _, 0x0, 0x7) => format!("LD V{:X}, DT", x),
+            (0xF, _, 0x0, 0xA) => format!("LD V{:X}, K", x),
+            (0xF, _, 0x1, 0x5) => format!("LD DT, V{:X}", x),
+            (0xF, _, 0x1, 0x8) => format!("LD ST, V{:X}", x),
+            (0xF, _, 0x1, 0xE) => format!("ADD I, V{:X}", x),
+            (0xF, _, 0x2, 0x9) => format!("LD F, V{:X}", x),
+            (0xF, _, 0x3, 0x3) => format!("LD B, V{:X}", x),
+            (0xF, _, 0x5, 0x5) => format!("LD [I], V{:X}", x),
+            (0xF, _, 0x6, 0x5) => format!("LD V{:X}, [I]", x),
+            _ => format!("??? {:#06X}", instruction),
+        }
+    }
+
+    /// Dumps all 16 registers, `I`, `DT`, `ST`, the stack, and the PC.
+    pub fn dump_registers(processor: &Processor) -> String {
+        let mut out = String::new();
+
+        for (i, value) in processor.registers().iter().enumerate() {
+            out.push_str(&format!("V{:X} = {:#04X}  ", i, value));
+
+            if i % 4 == 3 {
+                out.push('\n');
+            }
+        }
+
+        out.push_str(&format!(
+            "I  = {:#06X}  DT = {:#04X}  ST = {:#04X}  PC = {:#06X}\n",
+            processor.index(),
+            processor.delay_timer(),
+            processor.sound_timer(),
+            processor.program_counter(),
+        ));
+        out.push_str(&format!("Stack: {:?}\n", processor.stack()));
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // LD V0, 0x01; LD V1, 0x02; LD V2, 0x03
+    const ROM: [u8; 6] = [0x60, 0x01, 0x61, 0x02, 0x62, 0x03];
+
+    fn processor_with_rom() -> Processor {
+        let mut processor = Processor::new();
+        processor.load_rom_bytes(&ROM).unwrap();
+
+        processor
+    }
+
+    #[test]
+    fn step_executes_exactly_one_instruction() {
+        let mut processor = processor_with_rom();
+        let debugger = Debugger::new();
+
+        debugger.step(&mut processor).unwrap();
+
+        assert_eq!(processor.registers()[0], 0x01);
+        assert_eq!(processor.registers()[1], 0x00);
+    }
+
+    #[test]
+    fn breakpoints_can_be_added_and_removed() {
+        let mut debugger = Debugger::new();
+
+        debugger.add_breakpoint(0x204);
+        assert!(debugger.has_breakpoint(0x204));
+
+        debugger.remove_breakpoint(0x204);
+        assert!(!debugger.has_breakpoint(0x204));
+    }
+
+    #[test]
+    fn continue_run_stops_at_breakpoint_before_executing_it() {
+        let mut processor = processor_with_rom();
+        let mut debugger = Debugger::new();
+        debugger.add_breakpoint(0x204);
+
+        let outcome = debugger.continue_run(&mut processor).unwrap();
+
+        match outcome {
+            Outcome::Breakpoint(addr) => assert_eq!(addr, 0x204),
+        }
+        // The breakpointed instruction (LD V2, 0x03) must not have run yet.
+        assert_eq!(processor.registers()[2], 0x00);
+        assert_eq!(processor.registers()[0], 0x01);
+        assert_eq!(processor.registers()[1], 0x02);
+    }
+
+    #[test]
+    fn disassemble_decodes_common_opcodes() {
+        assert_eq!(Debugger::disassemble(0x00E0), "CLS");
+        assert_eq!(Debugger::disassemble(0x1234), "JP 0x234");
+        assert_eq!(Debugger::disassemble(0x6A05), "LD VA, 0x05");
+        assert_eq!(Debugger::disassemble(0xD015), "DRW V0, V1, 5");
+    }
+
+    #[test]
+    fn dump_registers_includes_pc_and_stack() {
+        let processor = processor_with_rom();
+        let dump = Debugger::dump_registers(&processor);
+
+        assert!(dump.contains("PC"));
+        assert!(dump.contains("Stack"));
+    }
+}
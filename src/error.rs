@@ -0,0 +1,65 @@
+use std::fmt;
+
+/// Distinguishes the different ways the CHIP-8 interpreter can fail, so
+/// callers can match on the kind instead of parsing an error message.
+#[derive(Debug)]
+pub enum ErrorKind {
+    UnknownOpcode(u16),
+    MemoryOutOfBounds { addr: u16 },
+    StackOverflow,
+    StackUnderflow,
+    InvalidRom,
+    InvalidSnapshot,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::UnknownOpcode(opcode) => write!(f, "unknown opcode {:#06X}", opcode),
+            ErrorKind::MemoryOutOfBounds { addr } => {
+                write!(f, "memory access out of bounds at {:#06X}", addr)
+            }
+            ErrorKind::StackOverflow => write!(f, "stack overflow"),
+            ErrorKind::StackUnderflow => write!(f, "stack underflow"),
+            ErrorKind::InvalidRom => write!(f, "invalid ROM"),
+            ErrorKind::InvalidSnapshot => write!(f, "invalid snapshot"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    message: Option<String>,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind) -> Error {
+        Error {
+            kind,
+            message: None,
+        }
+    }
+
+    pub fn with_message(kind: ErrorKind, message: impl Into<String>) -> Error {
+        Error {
+            kind,
+            message: Some(message.into()),
+        }
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}: {}", self.kind, message),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,164 @@
+use bit_vec::BitVec;
+use sdl2::event::Event;
+use sdl2::keyboard::Scancode;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+use sdl2::EventPump;
+use sdl2::Sdl;
+
+use crate::processor::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+
+/// Maps the physical `1234/QWER/ASDF/ZXCV` block onto the 16-key CHIP-8 keypad:
+///
+/// ```text
+/// 1 2 3 4        1 2 3 C
+/// Q W E R   ->   4 5 6 D
+/// A S D F        7 8 9 E
+/// Z X C V        A 0 B F
+/// ```
+fn map_key(scancode: Scancode) -> Option<usize> {
+    match scancode {
+        Scancode::Num1 => Some(0x1),
+        Scancode::Num2 => Some(0x2),
+        Scancode::Num3 => Some(0x3),
+        Scancode::Num4 => Some(0xC),
+        Scancode::Q => Some(0x4),
+        Scancode::W => Some(0x5),
+        Scancode::E => Some(0x6),
+        Scancode::R => Some(0xD),
+        Scancode::A => Some(0x7),
+        Scancode::S => Some(0x8),
+        Scancode::D => Some(0x9),
+        Scancode::F => Some(0xE),
+        Scancode::Z => Some(0xA),
+        Scancode::X => Some(0x0),
+        Scancode::C => Some(0xB),
+        Scancode::V => Some(0xF),
+        _ => None,
+    }
+}
+
+/// A single user input reported by [`Frontend::pump_events`].
+pub enum InputEvent {
+    /// A hex-keypad key went up or down.
+    Key(usize, bool),
+    /// F5: quick-save the current machine state.
+    QuickSave,
+    /// F9: quick-load the last quick-saved machine state.
+    QuickLoad,
+}
+
+/// SDL2-backed window, framebuffer renderer, and hex-keypad input source.
+pub struct Frontend {
+    canvas: Canvas<Window>,
+    event_pump: EventPump,
+    scale: u32,
+    foreground: Color,
+    background: Color,
+}
+
+impl Frontend {
+    pub fn new(
+        sdl_context: &Sdl,
+        scale: u32,
+        foreground: Color,
+        background: Color,
+    ) -> Result<Frontend, String> {
+        let video_subsystem = sdl_context.video()?;
+
+        let window = video_subsystem
+            .window(
+                "Chip8",
+                DISPLAY_WIDTH as u32 * scale,
+                DISPLAY_HEIGHT as u32 * scale,
+            )
+            .position_centered()
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        let mut canvas = window
+            .into_canvas()
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        canvas.set_draw_color(background);
+        canvas.clear();
+        canvas.present();
+
+        let event_pump = sdl_context.event_pump()?;
+
+        Ok(Frontend {
+            canvas,
+            event_pump,
+            scale,
+            foreground,
+            background,
+        })
+    }
+
+    /// Polls pending SDL events, reporting each as an [`InputEvent`] to `on_event`.
+    /// Returns `false` once the window has been asked to close.
+    pub fn pump_events(&mut self, mut on_event: impl FnMut(InputEvent)) -> bool {
+        for event in self.event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. } => return false,
+                Event::KeyDown {
+                    scancode: Some(Scancode::F5),
+                    ..
+                } => on_event(InputEvent::QuickSave),
+                Event::KeyDown {
+                    scancode: Some(Scancode::F9),
+                    ..
+                } => on_event(InputEvent::QuickLoad),
+                Event::KeyDown {
+                    scancode: Some(scancode),
+                    ..
+                } => {
+                    if let Some(key) = map_key(scancode) {
+                        on_event(InputEvent::Key(key, true));
+                    }
+                }
+                Event::KeyUp {
+                    scancode: Some(scancode),
+                    ..
+                } => {
+                    if let Some(key) = map_key(scancode) {
+                        on_event(InputEvent::Key(key, false));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        true
+    }
+
+    /// Blits the 64x32 monochrome framebuffer, scaled and tinted with the
+    /// configured foreground/background colors.
+    pub fn draw(&mut self, display: &BitVec) {
+        self.canvas.set_draw_color(self.background);
+        self.canvas.clear();
+
+        self.canvas.set_draw_color(self.foreground);
+        for y in 0..DISPLAY_HEIGHT {
+            for x in 0..DISPLAY_WIDTH {
+                if !display[y * DISPLAY_WIDTH + x] {
+                    continue;
+                }
+
+                let rect = Rect::new(
+                    (x as u32 * self.scale) as i32,
+                    (y as u32 * self.scale) as i32,
+                    self.scale,
+                    self.scale,
+                );
+
+                let _ = self.canvas.fill_rect(rect);
+            }
+        }
+
+        self.canvas.present();
+    }
+}
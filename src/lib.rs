@@ -0,0 +1,6 @@
+pub mod audio;
+pub mod debugger;
+pub mod error;
+pub mod frontend;
+pub mod processor;
+pub mod quirks;
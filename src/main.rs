@@ -1,156 +1,117 @@
+use std::env;
+use std::path::Path;
 use std::time::Duration;
 
-use bit_vec::BitVec;
-use chan::tick;
+use chan::{chan_select, tick};
+use sdl2::pixels::Color;
 
-const FONT: [u8; 80] = [
-    0xF0, 0x90, 0x90, 0x90, 0xF0, // 0
-    0x20, 0x60, 0x20, 0x20, 0x70, // 1
-    0xF0, 0x10, 0xF0, 0x80, 0xF0, // 2
-    0xF0, 0x10, 0xF0, 0x10, 0xF0, // 3
-    0x90, 0x90, 0xF0, 0x10, 0x10, // 4
-    0xF0, 0x80, 0xF0, 0x10, 0xF0, // 5
-    0xF0, 0x80, 0xF0, 0x90, 0xF0, // 6
-    0xF0, 0x10, 0x20, 0x40, 0x40, // 7
-    0xF0, 0x90, 0xF0, 0x90, 0xF0, // 8
-    0xF0, 0x90, 0xF0, 0x10, 0xF0, // 9
-    0xF0, 0x90, 0xF0, 0x90, 0x90, // A
-    0xE0, 0x90, 0xE0, 0x90, 0xE0, // B
-    0xF0, 0x80, 0x80, 0x80, 0xF0, // C
-    0xE0, 0x90, 0x90, 0x90, 0xE0, // D
-    0xF0, 0x80, 0xF0, 0x80, 0xF0, // E
-    0xF0, 0x80, 0xF0, 0x80, 0x80, // F
-];
+use chip8::audio::Beeper;
+use chip8::debugger::Debugger;
+use chip8::error::ErrorKind;
+use chip8::frontend::{Frontend, InputEvent};
+use chip8::processor::Processor;
 
-struct Processor {
-    instruction: u16,
-    program_counter: u16,
-    index: u16,
-    delay_timer: u16,
-    sound_timer: u16,
-    stack: Vec<u16>,
-    memory: Vec<u8>,
-    registers: Vec<u8>,
-    display: BitVec,
-    tick_rate: u64,
-}
-
-impl Processor {
-    pub fn new() -> Processor {
-        let mut processor = Processor {
-            instruction: 0x0,
-            program_counter: 0x50,
-            index: 0x0,
-            delay_timer: 0x0,
-            sound_timer: 0x0,
-            stack: Vec::new(),
-            memory: vec![0; 0x1000],
-            registers: vec![0, 0xF],
-            display: BitVec::from_elem(0x40 * 0x20, false),
-            tick_rate: 2, // default 700
-        };
-
-        processor.load_font();
-
-        processor
-    }
-
-    pub fn main_loop(&mut self) -> Result<(), String> {
-        let timer = tick(Duration::from_nanos(1000000000 / self.tick_rate));
-
-        loop {
-            timer.recv();
+const SCALE: u32 = 12;
+const FOREGROUND: Color = Color::RGB(0xFF, 0xFF, 0xFF);
+const BACKGROUND: Color = Color::RGB(0x00, 0x00, 0x00);
+const TIMER_RATE: u64 = 60;
 
-            self.fetch()?;
-            println!("Fetched: {:#06X}", self.instruction);
-            self.execute()?;
-        }
-    }
-
-    fn load_font(&mut self) {
-        let memory = &mut self.memory;
-
-        memory[0x50..0xA0].copy_from_slice(&FONT);
+fn main() {
+    let mut args = env::args().skip(1);
+    let mut arg = args
+        .next()
+        .expect("Usage: chip8 [--superchip] <rom path> [breakpoint ...]");
+
+    // `--superchip` selects the SUPER-CHIP quirks profile instead of the
+    // classic CHIP-8 one, so ROMs authored for SUPER-CHIP behave correctly.
+    let superchip = arg == "--superchip";
+    if superchip {
+        arg = args
+            .next()
+            .expect("Usage: chip8 [--superchip] <rom path> [breakpoint ...]");
     }
-
-    fn fetch(&mut self) -> Result<(), String> {
-        let memory = &mut self.memory;
-
-        if self.program_counter as usize > memory.len() - 2 {
-            return Err("Program counter out of bounds!".to_string());
-        }
-
-        self.instruction = ((memory[self.program_counter as usize] as u16) << 8)
-            | (memory[(self.program_counter + 1) as usize] as u16);
-
-        self.program_counter += 2;
-
-        Ok(())
+    let rom_path = arg;
+
+    let mut processor = if superchip {
+        Processor::new_superchip()
+    } else {
+        Processor::new()
+    };
+    processor
+        .load_rom(Path::new(&rom_path))
+        .expect("Failed to load ROM");
+    let save_state_path = Path::new(&rom_path).with_extension("sav");
+
+    let sdl_context = sdl2::init().expect("Failed to initialize SDL2");
+    let mut frontend = Frontend::new(&sdl_context, SCALE, FOREGROUND, BACKGROUND)
+        .expect("Failed to initialize SDL2 frontend");
+    let beeper = Beeper::new(&sdl_context).expect("Failed to initialize SDL2 audio");
+    let mut debugger = Debugger::new();
+
+    // Remaining args are breakpoint addresses in hex, e.g. `chip8 game.ch8 204 21a`.
+    for arg in args {
+        let addr = u16::from_str_radix(arg.trim_start_matches("0x"), 16)
+            .unwrap_or_else(|_| panic!("Invalid breakpoint address: {}", arg));
+        debugger.add_breakpoint(addr);
     }
 
-    fn execute(&mut self) -> Result<(), String> {
-        let stack = &mut self.stack;
-
-        let instruction = self.instruction;
-
-        let nibbles = (
-            (instruction & 0xF000) >> 12 as u8,
-            (instruction & 0x0F00) >> 8 as u8,
-            (instruction & 0x00F0) >> 4 as u8,
-            (instruction & 0x000F) as u8,
-        );
-
-        let x = nibbles.1; // high
-        let y = nibbles.2; // low
-        let n = nibbles.3; // nibble
-        let kk = instruction & 0x00FF; // byte
-        let nnn = instruction & 0x0FFF; // addr
-
-        match nibbles {
-            (0x0, 0x0, 0xE, 0x0) => {} // CLS
-            (0x0, 0x0, 0xE, 0xE) => {} // RET
-            (0x0, _, _, _) => {}       // SYS addr
-            (0x1, _, _, _) => {}       // JP addr
-            (0x2, _, _, _) => {}       // CALL addr
-            (0x3, _, _, _) => {}       // SE Vx, byte
-            (0x4, _, _, _) => {}       // SNE Vx, byte
-            (0x5, _, _, 0x0) => {}     // SE Vx, Vy
-            (0x6, _, _, _) => {}       // LD Vx, byte
-            (0x7, _, _, _) => {}       // ADD Vx, byte
-            (0x8, _, _, 0x0) => {}     // LD Vx, Vy
-            (0x8, _, _, 0x1) => {}     // OR Vx, Vy
-            (0x8, _, _, 0x2) => {}     // AND Vx, Vy
-            (0x8, _, _, 0x3) => {}     // XOR Vx, Vy
-            (0x8, _, _, 0x4) => {}     // ADD Vx, Vy
-            (0x8, _, _, 0x5) => {}     // SUB Vx, Vy
-            (0x8, _, _, 0x6) => {}     // SHR Vx {, Vy}
-            (0x8, _, _, 0x7) => {}     // SUBN Vx, Vy
-            (0x8, _, _, 0xE) => {}     // SHL Vx {, Vy}
-            (0x9, _, _, 0x0) => {}     // SNE Vx, Vy
-            (0xA, _, _, _) => {}       // LD I, addr
-            (0xB, _, _, _) => {}       // JP V0, addr
-            (0xC, _, _, _) => {}       // RND Vx, byte
-            (0xD, _, _, _) => {}       // DRW Vx, Vy, nibble
-            (0xE, _, 0x9, 0xE) => {}   // SKP Vx
-            (0xE, _, 0xA, 0x1) => {}   // SKNP Vx
-            (0xF, _, 0x0, 0x7) => {}   // LD Vx, DT
-            (0xF, _, 0x0, 0xA) => {}   // LD Vx, K
-            (0xF, _, 0x1, 0x5) => {}   // LD DT, Vx
-            (0xF, _, 0x1, 0x8) => {}   // LD ST, Vx
-            (0xF, _, 0x1, 0xE) => {}   // ADD I, Vx
-            (0xF, _, 0x2, 0x9) => {}   // LD F, Vx
-            (0xF, _, 0x3, 0x3) => {}   // LD B, Vx
-            (0xF, _, 0x5, 0x5) => {}   // LD [I], Vx
-            (0xF, _, 0x6, 0x5) => {}   // LD Vx, [I]
-            _ => return Err(format!("Invalid instruction: {:#06X}!", instruction)),
+    let cpu_ticker = tick(Duration::from_nanos(1_000_000_000 / processor.tick_rate()));
+    // Rendering and the delay/sound timers both run at the fixed 60 Hz CHIP-8
+    // rate, decoupled from the instruction throughput of `cpu_ticker`.
+    let timer_ticker = tick(Duration::from_nanos(1_000_000_000 / TIMER_RATE));
+
+    loop {
+        chan_select! {
+            cpu_ticker.recv() => {
+                let pc = processor.program_counter();
+
+                if debugger.has_breakpoint(pc) {
+                    match processor.peek_instruction(pc) {
+                        Ok(instruction) => println!(
+                            "Breakpoint hit at {:#06X}: {}",
+                            pc,
+                            Debugger::disassemble(instruction)
+                        ),
+                        Err(err) => eprintln!("{}", err),
+                    }
+                    println!("{}", Debugger::dump_registers(&processor));
+                    break;
+                }
+
+                if let Err(err) = debugger.step(&mut processor) {
+                    match err.kind() {
+                        // A garbled or data-as-code opcode shouldn't take the whole
+                        // interpreter down; log it and keep running.
+                        ErrorKind::UnknownOpcode(_) => eprintln!("{}", err),
+                        _ => {
+                            eprintln!("{}", err);
+                            break;
+                        }
+                    }
+                }
+            },
+            timer_ticker.recv() => {
+                let keep_running = frontend.pump_events(|event| match event {
+                    InputEvent::Key(key, pressed) => processor.set_key(key, pressed),
+                    InputEvent::QuickSave => {
+                        if let Err(err) = processor.save_state_to_file(&save_state_path) {
+                            eprintln!("{}", err);
+                        }
+                    }
+                    InputEvent::QuickLoad => {
+                        if let Err(err) = processor.load_state_from_file(&save_state_path) {
+                            eprintln!("{}", err);
+                        }
+                    }
+                });
+                if !keep_running {
+                    break;
+                }
+
+                processor.tick_timers();
+                beeper.set_active(processor.is_sound_active());
+                frontend.draw(processor.display());
+            },
         }
-
-        Ok(())
     }
 }
-
-fn main() {
-    let mut processor = Processor::new();
-
-    println!("{}", processor.main_loop().err().unwrap());
-}
\ No newline at end of file
@@ -0,0 +1,700 @@
+use std::convert::TryInto;
+use std::fs;
+use std::path::Path;
+
+use bit_vec::BitVec;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::error::{Error, ErrorKind};
+use crate::quirks::{LoadStoreIncrement, Quirks};
+
+const ROM_START: usize = 0x200;
+const MAX_STACK_DEPTH: usize = 16;
+
+pub const DISPLAY_WIDTH: usize = 64;
+pub const DISPLAY_HEIGHT: usize = 32;
+
+const FONT: [u8; 80] = [
+    0xF0, 0x90, 0x90, 0x90, 0xF0, // 0
+    0x20, 0x60, 0x20, 0x20, 0x70, // 1
+    0xF0, 0x10, 0xF0, 0x80, 0xF0, // 2
+    0xF0, 0x10, 0xF0, 0x10, 0xF0, // 3
+    0x90, 0x90, 0xF0, 0x10, 0x10, // 4
+    0xF0, 0x80, 0xF0, 0x10, 0xF0, // 5
+    0xF0, 0x80, 0xF0, 0x90, 0xF0, // 6
+    0xF0, 0x10, 0x20, 0x40, 0x40, // 7
+    0xF0, 0x90, 0xF0, 0x90, 0xF0, // 8
+    0xF0, 0x90, 0xF0, 0x10, 0xF0, // 9
+    0xF0, 0x90, 0xF0, 0x90, 0x90, // A
+    0xE0, 0x90, 0xE0, 0x90, 0xE0, // B
+    0xF0, 0x80, 0x80, 0x80, 0xF0, // C
+    0xE0, 0x90, 0x90, 0x90, 0xE0, // D
+    0xF0, 0x80, 0xF0, 0x80, 0xF0, // E
+    0xF0, 0x80, 0xF0, 0x80, 0x80, // F
+];
+
+pub struct Processor {
+    instruction: u16,
+    program_counter: u16,
+    index: u16,
+    delay_timer: u16,
+    sound_timer: u16,
+    stack: Vec<u16>,
+    memory: Vec<u8>,
+    registers: Vec<u8>,
+    display: BitVec,
+    keypad: [bool; 16],
+    rng: StdRng,
+    rng_seed: [u8; 32],
+    rng_draws: u64,
+    tick_rate: u64,
+    quirks: Quirks,
+}
+
+impl Default for Processor {
+    fn default() -> Processor {
+        Processor::new()
+    }
+}
+
+impl Processor {
+    /// Creates a processor with the classic CHIP-8 quirk profile.
+    pub fn new() -> Processor {
+        Processor::new_chip8()
+    }
+
+    pub fn new_chip8() -> Processor {
+        Processor::with_quirks(Quirks::chip8())
+    }
+
+    pub fn new_superchip() -> Processor {
+        Processor::with_quirks(Quirks::superchip())
+    }
+
+    pub fn with_quirks(quirks: Quirks) -> Processor {
+        let rng_seed: [u8; 32] = rand::random();
+
+        let mut processor = Processor {
+            instruction: 0x0,
+            program_counter: ROM_START as u16,
+            index: 0x0,
+            delay_timer: 0x0,
+            sound_timer: 0x0,
+            stack: Vec::new(),
+            memory: vec![0; 0x1000],
+            registers: vec![0u8; 16],
+            display: BitVec::from_elem(DISPLAY_WIDTH * DISPLAY_HEIGHT, false),
+            keypad: [false; 16],
+            rng: StdRng::from_seed(rng_seed),
+            rng_seed,
+            rng_draws: 0,
+            tick_rate: 2, // default 700
+            quirks,
+        };
+
+        processor.load_font();
+
+        processor
+    }
+
+    pub fn tick_rate(&self) -> u64 {
+        self.tick_rate
+    }
+
+    pub fn display(&self) -> &BitVec {
+        &self.display
+    }
+
+    pub fn program_counter(&self) -> u16 {
+        self.program_counter
+    }
+
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    pub fn delay_timer(&self) -> u16 {
+        self.delay_timer
+    }
+
+    pub fn sound_timer(&self) -> u16 {
+        self.sound_timer
+    }
+
+    pub fn registers(&self) -> &[u8] {
+        &self.registers
+    }
+
+    pub fn stack(&self) -> &[u16] {
+        &self.stack
+    }
+
+    /// Reads the instruction at `addr` without fetching it (i.e. without
+    /// advancing the program counter), for disassembly.
+    pub fn peek_instruction(&self, addr: u16) -> Result<u16, Error> {
+        self.check_memory_bounds(addr, 2)?;
+
+        Ok(((self.memory[addr as usize] as u16) << 8) | (self.memory[addr as usize + 1] as u16))
+    }
+
+    pub fn set_key(&mut self, key: usize, pressed: bool) {
+        self.keypad[key] = pressed;
+    }
+
+    pub fn is_sound_active(&self) -> bool {
+        self.sound_timer > 0
+    }
+
+    /// Decrements the delay and sound timers. Must be driven at the fixed
+    /// 60 Hz CHIP-8 timer rate, independent of `tick_rate`.
+    pub fn tick_timers(&mut self) {
+        if self.delay_timer > 0 {
+            self.delay_timer -= 1;
+        }
+
+        if self.sound_timer > 0 {
+            self.sound_timer -= 1;
+        }
+    }
+
+    /// Fetches and executes a single instruction.
+    pub fn cycle(&mut self) -> Result<(), Error> {
+        self.fetch()?;
+        self.execute()
+    }
+
+    /// Checks that `[addr, addr + len)` falls within `memory`, so opcodes
+    /// that derive an address from instruction operands (rather than the
+    /// program counter) can't panic on a malformed ROM.
+    fn check_memory_bounds(&self, addr: u16, len: usize) -> Result<(), Error> {
+        if addr as usize + len > self.memory.len() {
+            return Err(Error::new(ErrorKind::MemoryOutOfBounds { addr }));
+        }
+
+        Ok(())
+    }
+
+    fn load_store_increment(&self, x: usize) -> u16 {
+        match self.quirks.load_store_increment {
+            LoadStoreIncrement::ByXPlusOne => x as u16 + 1,
+            LoadStoreIncrement::ByX => x as u16,
+            LoadStoreIncrement::None => 0,
+        }
+    }
+
+    /// Captures the full machine state (registers, `I`, `PC`, `DT`, `ST`,
+    /// the stack, memory, display, and RNG seed + draw count) as an opaque
+    /// byte blob.
+    pub fn save_state(&self) -> Vec<u8> {
+        let mut state = Vec::new();
+
+        state.extend_from_slice(&self.instruction.to_le_bytes());
+        state.extend_from_slice(&self.program_counter.to_le_bytes());
+        state.extend_from_slice(&self.index.to_le_bytes());
+        state.extend_from_slice(&self.delay_timer.to_le_bytes());
+        state.extend_from_slice(&self.sound_timer.to_le_bytes());
+        state.extend_from_slice(&self.tick_rate.to_le_bytes());
+        state.extend_from_slice(&self.rng_seed);
+        state.extend_from_slice(&self.rng_draws.to_le_bytes());
+        state.extend_from_slice(&self.quirks.to_bytes());
+        state.extend_from_slice(&self.registers);
+
+        state.extend_from_slice(&(self.stack.len() as u16).to_le_bytes());
+        for value in &self.stack {
+            state.extend_from_slice(&value.to_le_bytes());
+        }
+
+        for &pressed in &self.keypad {
+            state.push(pressed as u8);
+        }
+
+        state.extend_from_slice(&self.memory);
+        state.extend_from_slice(&self.display.to_bytes());
+
+        state
+    }
+
+    /// Restores state captured by [`Processor::save_state`], including
+    /// reseeding the RNG so a restored session replays identically.
+    pub fn load_state(&mut self, data: &[u8]) -> Result<(), Error> {
+        let mut cursor = 0usize;
+
+        let instruction = u16::from_le_bytes(take(data, &mut cursor, 2)?.try_into().unwrap());
+        let program_counter = u16::from_le_bytes(take(data, &mut cursor, 2)?.try_into().unwrap());
+        let index = u16::from_le_bytes(take(data, &mut cursor, 2)?.try_into().unwrap());
+        let delay_timer = u16::from_le_bytes(take(data, &mut cursor, 2)?.try_into().unwrap());
+        let sound_timer = u16::from_le_bytes(take(data, &mut cursor, 2)?.try_into().unwrap());
+        let tick_rate = u64::from_le_bytes(take(data, &mut cursor, 8)?.try_into().unwrap());
+        let rng_seed: [u8; 32] = take(data, &mut cursor, 32)?.try_into().unwrap();
+        let rng_draws = u64::from_le_bytes(take(data, &mut cursor, 8)?.try_into().unwrap());
+        let quirks = Quirks::from_bytes(take(data, &mut cursor, 4)?.try_into().unwrap());
+        let registers = take(data, &mut cursor, 16)?.to_vec();
+
+        let stack_len = u16::from_le_bytes(take(data, &mut cursor, 2)?.try_into().unwrap()) as usize;
+        let mut stack = Vec::with_capacity(stack_len);
+        for _ in 0..stack_len {
+            stack.push(u16::from_le_bytes(take(data, &mut cursor, 2)?.try_into().unwrap()));
+        }
+
+        let mut keypad = [false; 16];
+        for (slot, &byte) in keypad.iter_mut().zip(take(data, &mut cursor, 16)?) {
+            *slot = byte != 0;
+        }
+
+        let memory = take(data, &mut cursor, 0x1000)?.to_vec();
+        let display =
+            BitVec::from_bytes(take(data, &mut cursor, DISPLAY_WIDTH * DISPLAY_HEIGHT / 8)?);
+
+        self.instruction = instruction;
+        self.program_counter = program_counter;
+        self.index = index;
+        self.delay_timer = delay_timer;
+        self.sound_timer = sound_timer;
+        self.tick_rate = tick_rate;
+        self.rng_seed = rng_seed;
+        self.rng_draws = rng_draws;
+        self.rng = StdRng::from_seed(rng_seed);
+        // StdRng can't be serialized directly, so fast-forward a fresh one
+        // seeded the same way back to the stream position it was captured
+        // at, otherwise a restored session would replay RND from the start.
+        for _ in 0..rng_draws {
+            self.rng.gen::<u8>();
+        }
+        self.quirks = quirks;
+        self.registers = registers;
+        self.stack = stack;
+        self.keypad = keypad;
+        self.memory = memory;
+        self.display = display;
+
+        Ok(())
+    }
+
+    pub fn save_state_to_file(&self, path: &Path) -> Result<(), Error> {
+        fs::write(path, self.save_state())
+            .map_err(|err| Error::with_message(ErrorKind::InvalidSnapshot, err.to_string()))
+    }
+
+    pub fn load_state_from_file(&mut self, path: &Path) -> Result<(), Error> {
+        let data = fs::read(path)
+            .map_err(|err| Error::with_message(ErrorKind::InvalidSnapshot, err.to_string()))?;
+
+        self.load_state(&data)
+    }
+
+    fn load_font(&mut self) {
+        let memory = &mut self.memory;
+
+        memory[0x50..0xA0].copy_from_slice(&FONT);
+    }
+
+    pub fn load_rom(&mut self, path: &Path) -> Result<(), Error> {
+        let rom = fs::read(path)
+            .map_err(|err| Error::with_message(ErrorKind::InvalidRom, err.to_string()))?;
+
+        self.load_rom_bytes(&rom)
+    }
+
+    pub fn load_rom_bytes(&mut self, rom: &[u8]) -> Result<(), Error> {
+        if rom.len() > self.memory.len() - ROM_START {
+            return Err(Error::with_message(
+                ErrorKind::InvalidRom,
+                "ROM is too large to fit in memory",
+            ));
+        }
+
+        self.memory[ROM_START..ROM_START + rom.len()].copy_from_slice(rom);
+        self.program_counter = ROM_START as u16;
+
+        Ok(())
+    }
+
+    fn fetch(&mut self) -> Result<(), Error> {
+        let memory = &mut self.memory;
+
+        if self.program_counter as usize > memory.len() - 2 {
+            return Err(Error::new(ErrorKind::MemoryOutOfBounds {
+                addr: self.program_counter,
+            }));
+        }
+
+        self.instruction = ((memory[self.program_counter as usize] as u16) << 8)
+            | (memory[(self.program_counter + 1) as usize] as u16);
+
+        self.program_counter += 2;
+
+        Ok(())
+    }
+
+    fn execute(&mut self) -> Result<(), Error> {
+        let instruction = self.instruction;
+
+        let nibbles = (
+            ((instruction & 0xF000) >> 12) as u8,
+            ((instruction & 0x0F00) >> 8) as u8,
+            ((instruction & 0x00F0) >> 4) as u8,
+            (instruction & 0x000F) as u8,
+        );
+
+        let x = nibbles.1 as usize; // high
+        let y = nibbles.2 as usize; // low
+        let n = nibbles.3 as usize; // nibble
+        let kk = (instruction & 0x00FF) as u8; // byte
+        let nnn = instruction & 0x0FFF; // addr
+
+        match nibbles {
+            (0x0, 0x0, 0xE, 0x0) => self.display.clear(), // CLS
+            (0x0, 0x0, 0xE, 0xE) => {
+                // RET
+                self.program_counter = self
+                    .stack
+                    .pop()
+                    .ok_or_else(|| Error::new(ErrorKind::StackUnderflow))?;
+            }
+            (0x0, _, _, _) => {} // SYS addr (ignored on modern interpreters)
+            (0x1, _, _, _) => self.program_counter = nnn, // JP addr
+            (0x2, _, _, _) => {
+                // CALL addr
+                if self.stack.len() >= MAX_STACK_DEPTH {
+                    return Err(Error::new(ErrorKind::StackOverflow));
+                }
+
+                self.stack.push(self.program_counter);
+                self.program_counter = nnn;
+            }
+            (0x3, _, _, _) => {
+                // SE Vx, byte
+                if self.registers[x] == kk {
+                    self.program_counter += 2;
+                }
+            }
+            (0x4, _, _, _) => {
+                // SNE Vx, byte
+                if self.registers[x] != kk {
+                    self.program_counter += 2;
+                }
+            }
+            (0x5, _, _, 0x0) => {
+                // SE Vx, Vy
+                if self.registers[x] == self.registers[y] {
+                    self.program_counter += 2;
+                }
+            }
+            (0x6, _, _, _) => self.registers[x] = kk, // LD Vx, byte
+            (0x7, _, _, _) => self.registers[x] = self.registers[x].wrapping_add(kk), // ADD Vx, byte
+            (0x8, _, _, 0x0) => self.registers[x] = self.registers[y], // LD Vx, Vy
+            (0x8, _, _, 0x1) => self.registers[x] |= self.registers[y], // OR Vx, Vy
+            (0x8, _, _, 0x2) => self.registers[x] &= self.registers[y], // AND Vx, Vy
+            (0x8, _, _, 0x3) => self.registers[x] ^= self.registers[y], // XOR Vx, Vy
+            (0x8, _, _, 0x4) => {
+                // ADD Vx, Vy
+                let sum = self.registers[x] as u16 + self.registers[y] as u16;
+                self.registers[0xF] = (sum > 0xFF) as u8;
+                self.registers[x] = sum as u8;
+            }
+            (0x8, _, _, 0x5) => {
+                // SUB Vx, Vy
+                let (vx, vy) = (self.registers[x], self.registers[y]);
+                self.registers[0xF] = (vx >= vy) as u8;
+                self.registers[x] = vx.wrapping_sub(vy);
+            }
+            (0x8, _, _, 0x6) => {
+                // SHR Vx {, Vy}
+                let source = if self.quirks.shift_in_place {
+                    self.registers[x]
+                } else {
+                    self.registers[y]
+                };
+                self.registers[0xF] = source & 0x1;
+                self.registers[x] = source >> 1;
+            }
+            (0x8, _, _, 0x7) => {
+                // SUBN Vx, Vy
+                let (vx, vy) = (self.registers[x], self.registers[y]);
+                self.registers[0xF] = (vy >= vx) as u8;
+                self.registers[x] = vy.wrapping_sub(vx);
+            }
+            (0x8, _, _, 0xE) => {
+                // SHL Vx {, Vy}
+                let source = if self.quirks.shift_in_place {
+                    self.registers[x]
+                } else {
+                    self.registers[y]
+                };
+                self.registers[0xF] = (source >> 7) & 0x1;
+                self.registers[x] = source << 1;
+            }
+            (0x9, _, _, 0x0) => {
+                // SNE Vx, Vy
+                if self.registers[x] != self.registers[y] {
+                    self.program_counter += 2;
+                }
+            }
+            (0xA, _, _, _) => self.index = nnn, // LD I, addr
+            (0xB, _, _, _) => {
+                // JP V0, addr (or JP Vx, xnn under the SUPER-CHIP quirk)
+                self.program_counter = if self.quirks.jump_with_vx {
+                    nnn + self.registers[x] as u16
+                } else {
+                    nnn + self.registers[0x0] as u16
+                };
+            }
+            (0xC, _, _, _) => {
+                // RND Vx, byte
+                self.rng_draws += 1;
+                self.registers[x] = self.rng.gen::<u8>() & kk;
+            }
+            (0xD, _, _, _) => {
+                // DRW Vx, Vy, nibble
+                let origin_x = self.registers[x] as usize % DISPLAY_WIDTH;
+                let origin_y = self.registers[y] as usize % DISPLAY_HEIGHT;
+                self.check_memory_bounds(self.index, n)?;
+                self.registers[0xF] = 0;
+
+                for row in 0..n {
+                    let sprite_byte = self.memory[self.index as usize + row];
+
+                    for col in 0..8 {
+                        if sprite_byte & (0x80 >> col) == 0 {
+                            continue;
+                        }
+
+                        let (raw_x, raw_y) = (origin_x + col, origin_y + row);
+                        if self.quirks.clip_sprites
+                            && (raw_x >= DISPLAY_WIDTH || raw_y >= DISPLAY_HEIGHT)
+                        {
+                            continue;
+                        }
+
+                        let px = raw_x % DISPLAY_WIDTH;
+                        let py = raw_y % DISPLAY_HEIGHT;
+                        let pixel_index = py * DISPLAY_WIDTH + px;
+
+                        if self.display[pixel_index] {
+                            self.registers[0xF] = 1;
+                        }
+
+                        let flipped = self.display[pixel_index] ^ true;
+                        self.display.set(pixel_index, flipped);
+                    }
+                }
+            }
+            (0xE, _, 0x9, 0xE) => {
+                // SKP Vx (key index is a nibble; mask so an out-of-range Vx
+                // can't index past the 16-key pad)
+                if self.keypad[(self.registers[x] & 0x0F) as usize] {
+                    self.program_counter += 2;
+                }
+            }
+            (0xE, _, 0xA, 0x1) => {
+                // SKNP Vx
+                if !self.keypad[(self.registers[x] & 0x0F) as usize] {
+                    self.program_counter += 2;
+                }
+            }
+            (0xF, _, 0x0, 0x7) => self.registers[x] = self.delay_timer as u8, // LD Vx, DT
+            (0xF, _, 0x0, 0xA) => {
+                // LD Vx, K
+                match self.keypad.iter().position(|&pressed| pressed) {
+                    Some(key) => self.registers[x] = key as u8,
+                    None => self.program_counter -= 2,
+                }
+            }
+            (0xF, _, 0x1, 0x5) => self.delay_timer = self.registers[x] as u16, // LD DT, Vx
+            (0xF, _, 0x1, 0x8) => self.sound_timer = self.registers[x] as u16, // LD ST, Vx
+            (0xF, _, 0x1, 0xE) => self.index = self.index.wrapping_add(self.registers[x] as u16), // ADD I, Vx
+            (0xF, _, 0x2, 0x9) => self.index = 0x50 + self.registers[x] as u16 * 5, // LD F, Vx
+            (0xF, _, 0x3, 0x3) => {
+                // LD B, Vx
+                self.check_memory_bounds(self.index, 3)?;
+
+                let vx = self.registers[x];
+                self.memory[self.index as usize] = vx / 100;
+                self.memory[self.index as usize + 1] = (vx / 10) % 10;
+                self.memory[self.index as usize + 2] = vx % 10;
+            }
+            (0xF, _, 0x5, 0x5) => {
+                // LD [I], Vx
+                self.check_memory_bounds(self.index, x + 1)?;
+
+                for offset in 0..=x {
+                    self.memory[self.index as usize + offset] = self.registers[offset];
+                }
+                self.index = self.index.wrapping_add(self.load_store_increment(x));
+            }
+            (0xF, _, 0x6, 0x5) => {
+                // LD Vx, [I]
+                self.check_memory_bounds(self.index, x + 1)?;
+
+                for offset in 0..=x {
+                    self.registers[offset] = self.memory[self.index as usize + offset];
+                }
+                self.index = self.index.wrapping_add(self.load_store_increment(x));
+            }
+            _ => return Err(Error::new(ErrorKind::UnknownOpcode(instruction))),
+        }
+
+        Ok(())
+    }
+}
+
+/// Slices the next `len` bytes out of `data`, advancing `cursor` past them.
+fn take<'a>(data: &'a [u8], cursor: &mut usize, len: usize) -> Result<&'a [u8], Error> {
+    let end = *cursor + len;
+    let slice = data
+        .get(*cursor..end)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidSnapshot))?;
+
+    *cursor = end;
+
+    Ok(slice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drw_out_of_bounds_index_returns_error_instead_of_panicking() {
+        // LD I, 0xFFF; DRW V0, V1, 15
+        let rom = [0xAF, 0xFF, 0xD0, 0x1F];
+        let mut processor = Processor::new();
+        processor.load_rom_bytes(&rom).unwrap();
+
+        processor.cycle().unwrap();
+        let err = processor.cycle().unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::MemoryOutOfBounds { .. }));
+    }
+
+    #[test]
+    fn skp_clamps_an_out_of_range_key_index_instead_of_panicking() {
+        // LD V0, 0xFF; SKP V0
+        let rom = [0x60, 0xFF, 0xE0, 0x9E];
+        let mut processor = Processor::new();
+        processor.load_rom_bytes(&rom).unwrap();
+
+        processor.cycle().unwrap();
+        processor.cycle().unwrap();
+
+        assert_eq!(processor.program_counter(), ROM_START as u16 + 4);
+    }
+
+    #[test]
+    fn save_state_round_trips_a_known_machine_state() {
+        // LD V0, 0x42; LD I, 0x300
+        let rom = [0x60, 0x42, 0xA3, 0x00];
+        let mut processor = Processor::new();
+        processor.load_rom_bytes(&rom).unwrap();
+        processor.cycle().unwrap();
+        processor.cycle().unwrap();
+
+        let snapshot = processor.save_state();
+
+        let mut restored = Processor::new();
+        restored.load_state(&snapshot).unwrap();
+
+        assert_eq!(restored.registers()[0], 0x42);
+        assert_eq!(restored.index(), 0x300);
+        assert_eq!(restored.program_counter(), processor.program_counter());
+    }
+
+    #[test]
+    fn restored_state_continues_the_rng_stream_instead_of_rewinding_it() {
+        // RND V0, 0xFF, three times.
+        let rom = [0xC0, 0xFF, 0xC0, 0xFF, 0xC0, 0xFF];
+        let mut processor = Processor::new();
+        processor.load_rom_bytes(&rom).unwrap();
+        processor.cycle().unwrap();
+        processor.cycle().unwrap();
+
+        let snapshot = processor.save_state();
+
+        // A fresh processor replaying the same two draws from the same seed
+        // should land on the same RNG stream position as the snapshot.
+        let mut restored = Processor::new();
+        restored.load_state(&snapshot).unwrap();
+        processor.cycle().unwrap();
+        restored.cycle().unwrap();
+
+        assert_eq!(processor.registers()[0], restored.registers()[0]);
+    }
+
+    #[test]
+    fn peek_instruction_out_of_bounds_returns_error_instead_of_panicking() {
+        let processor = Processor::new();
+
+        let err = processor.peek_instruction(0xFFF).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::MemoryOutOfBounds { .. }));
+    }
+
+    #[test]
+    fn add_vx_vy_sets_carry_flag_on_overflow() {
+        // LD V0, 0xFF; LD V1, 0x02; ADD V0, V1
+        let rom = [0x60, 0xFF, 0x61, 0x02, 0x80, 0x14];
+        let mut processor = Processor::new();
+        processor.load_rom_bytes(&rom).unwrap();
+
+        for _ in 0..3 {
+            processor.cycle().unwrap();
+        }
+
+        assert_eq!(processor.registers()[0], 0x01);
+        assert_eq!(processor.registers()[0xF], 1);
+    }
+
+    #[test]
+    fn sub_vx_vy_clears_borrow_flag_on_underflow() {
+        // LD V0, 0x01; LD V1, 0x02; SUB V0, V1
+        let rom = [0x60, 0x01, 0x61, 0x02, 0x80, 0x15];
+        let mut processor = Processor::new();
+        processor.load_rom_bytes(&rom).unwrap();
+
+        for _ in 0..3 {
+            processor.cycle().unwrap();
+        }
+
+        assert_eq!(processor.registers()[0], 0xFF);
+        assert_eq!(processor.registers()[0xF], 0);
+    }
+
+    #[test]
+    fn drw_sets_vf_on_pixel_collision() {
+        let mut processor = Processor::new();
+
+        // LD I, 0x300; LD V0, 0x80; LD [I], V0 -- seed a single on-pixel sprite byte.
+        processor
+            .load_rom_bytes(&[0xA3, 0x00, 0x60, 0x80, 0xF0, 0x55])
+            .unwrap();
+        for _ in 0..3 {
+            processor.cycle().unwrap();
+        }
+
+        // LD I, 0x300; LD V0, 0; LD V1, 0; DRW V0, V1, 1 (twice: draw, then collide+erase).
+        processor
+            .load_rom_bytes(&[0xA3, 0x00, 0x60, 0x00, 0x61, 0x00, 0xD0, 0x11, 0xD0, 0x11])
+            .unwrap();
+        for _ in 0..4 {
+            processor.cycle().unwrap();
+        }
+        assert_eq!(processor.registers()[0xF], 0);
+
+        processor.cycle().unwrap();
+        assert_eq!(processor.registers()[0xF], 1);
+    }
+
+    #[test]
+    fn fx55_out_of_bounds_index_returns_error_instead_of_panicking() {
+        // LD I, 0xFFF; LD [I], V1 (two bytes, the second past the end of memory)
+        let rom = [0xAF, 0xFF, 0xF1, 0x55];
+        let mut processor = Processor::new();
+        processor.load_rom_bytes(&rom).unwrap();
+
+        processor.cycle().unwrap();
+        let err = processor.cycle().unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::MemoryOutOfBounds { .. }));
+    }
+}
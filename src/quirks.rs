@@ -0,0 +1,85 @@
+/// Selects how a handful of opcodes behave, since their semantics diverged
+/// between the original COSMAC VIP CHIP-8 interpreter and SUPER-CHIP, and
+/// ROMs can rely on either.
+#[derive(Debug, Clone, Copy)]
+pub struct Quirks {
+    /// `8xy6`/`8xyE`: shift `Vx` in place (SUPER-CHIP), or copy `Vy` into
+    /// `Vx` and shift that (classic).
+    pub shift_in_place: bool,
+    /// `Fx55`/`Fx65`: how far `I` advances afterward.
+    pub load_store_increment: LoadStoreIncrement,
+    /// `Bnnn`: jump to `xnn + Vx` (SUPER-CHIP), or to `nnn + V0` (classic).
+    pub jump_with_vx: bool,
+    /// `Dxyn`: clip sprites at the screen edge instead of wrapping.
+    pub clip_sprites: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum LoadStoreIncrement {
+    ByXPlusOne,
+    ByX,
+    None,
+}
+
+impl Quirks {
+    pub fn chip8() -> Quirks {
+        Quirks {
+            shift_in_place: false,
+            load_store_increment: LoadStoreIncrement::ByXPlusOne,
+            jump_with_vx: false,
+            clip_sprites: false,
+        }
+    }
+
+    pub fn superchip() -> Quirks {
+        Quirks {
+            shift_in_place: true,
+            load_store_increment: LoadStoreIncrement::None,
+            jump_with_vx: true,
+            clip_sprites: true,
+        }
+    }
+
+    /// Packs the quirk flags into a fixed 4-byte encoding, for snapshotting.
+    pub fn to_bytes(self) -> [u8; 4] {
+        [
+            self.shift_in_place as u8,
+            self.load_store_increment.to_byte(),
+            self.jump_with_vx as u8,
+            self.clip_sprites as u8,
+        ]
+    }
+
+    pub fn from_bytes(bytes: [u8; 4]) -> Quirks {
+        Quirks {
+            shift_in_place: bytes[0] != 0,
+            load_store_increment: LoadStoreIncrement::from_byte(bytes[1]),
+            jump_with_vx: bytes[2] != 0,
+            clip_sprites: bytes[3] != 0,
+        }
+    }
+}
+
+impl LoadStoreIncrement {
+    fn to_byte(self) -> u8 {
+        match self {
+            LoadStoreIncrement::ByXPlusOne => 0,
+            LoadStoreIncrement::ByX => 1,
+            LoadStoreIncrement::None => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> LoadStoreIncrement {
+        match byte {
+            1 => LoadStoreIncrement::ByX,
+            2 => LoadStoreIncrement::None,
+            _ => LoadStoreIncrement::ByXPlusOne,
+        }
+    }
+}
+
+impl Default for Quirks {
+    fn default() -> Quirks {
+        Quirks::chip8()
+    }
+}